@@ -0,0 +1,21 @@
+mod texture_atlas_layout;
+mod texture_atlas_layout_loader;
+mod texture_atlas_merge;
+
+pub use texture_atlas_layout::*;
+pub use texture_atlas_layout_loader::*;
+pub use texture_atlas_merge::*;
+
+use bevy_app::{App, Plugin};
+use bevy_asset::AssetApp;
+
+/// Adds support for 2D sprites and texture atlases.
+#[derive(Default)]
+pub struct SpritePlugin;
+
+impl Plugin for SpritePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<TextureAtlasLayout>()
+            .init_asset_loader::<TextureAtlasLayoutLoader>();
+    }
+}
@@ -27,6 +27,18 @@ pub struct TextureAtlasLayout {
     ///
     /// [`TextureAtlasBuilder`]: crate::TextureAtlasBuilder
     pub texture_handles: Option<HashMap<Handle<Image>, usize>>,
+    /// Frame name to area index mapping. Set by loaders that read named-frame
+    /// atlas descriptions, such as [`TextureAtlasLayoutLoader`].
+    ///
+    /// [`TextureAtlasLayoutLoader`]: crate::TextureAtlasLayoutLoader
+    pub texture_names: Option<HashMap<String, usize>>,
+    /// For trimmed frames, the area index to the section's rect within its original,
+    /// untrimmed source image. Set by loaders that read trimmed-frame atlas
+    /// descriptions, such as [`TextureAtlasLayoutLoader`]. A section with no entry here
+    /// was not trimmed, and its `textures` rect already covers the full source image.
+    ///
+    /// [`TextureAtlasLayoutLoader`]: crate::TextureAtlasLayoutLoader
+    pub source_rects: Option<HashMap<usize, Rect>>,
 }
 
 impl TextureAtlasLayout {
@@ -35,6 +47,8 @@ impl TextureAtlasLayout {
         Self {
             size: dimensions,
             texture_handles: None,
+            texture_names: None,
+            source_rects: None,
             textures: Vec::new(),
         }
     }
@@ -90,6 +104,80 @@ impl TextureAtlasLayout {
             size: ((tile_size + current_padding) * grid_size) - current_padding,
             textures: sprites,
             texture_handles: None,
+            texture_names: None,
+            source_rects: None,
+        }
+    }
+
+    /// Generate a [`TextureAtlasLayout`] as a ragged grid, where each row can have a
+    /// different number of `tile_size` by `tile_size` cells. This is useful for sheets
+    /// packed by hand where an animation strip has a trailing partial row, or where
+    /// different strips have different lengths, and a uniform `columns × rows` grid
+    /// (as produced by [`from_grid`]) would either miss cells or include empty ones.
+    ///
+    /// `margin` is the space around the outside of the grid (equivalent to `offset` in
+    /// [`from_grid`]), while `spacing` is the space between adjacent cells (equivalent
+    /// to `padding` in [`from_grid`]). Resulting layout is indexed left to right, top
+    /// to bottom, row by row in the order given by `columns_per_row`.
+    ///
+    /// # Arguments
+    ///
+    /// * `tile_size` - Each layout grid cell size
+    /// * `columns_per_row` - Column count for each row, top to bottom
+    /// * `spacing` - Optional spacing between cells
+    /// * `margin` - Optional margin around the outside of the grid
+    ///
+    /// [`from_grid`]: Self::from_grid
+    pub fn from_grid_with_layout(
+        tile_size: Vec2,
+        columns_per_row: &[usize],
+        spacing: Option<Vec2>,
+        margin: Option<Vec2>,
+    ) -> Self {
+        let spacing = spacing.unwrap_or_default();
+        let margin = margin.unwrap_or_default();
+        let mut sprites = Vec::new();
+        let mut current_padding = Vec2::ZERO;
+        let mut max_columns = 0;
+
+        for (y, &columns) in columns_per_row.iter().enumerate() {
+            if y > 0 {
+                current_padding.y = spacing.y;
+            }
+            current_padding.x = 0.0;
+            max_columns = max_columns.max(columns);
+
+            for x in 0..columns {
+                if x > 0 {
+                    current_padding.x = spacing.x;
+                }
+
+                let cell = Vec2::new(x as f32, y as f32);
+
+                let rect_min = (tile_size + current_padding) * cell + margin;
+
+                sprites.push(Rect {
+                    min: rect_min,
+                    max: rect_min + tile_size,
+                });
+            }
+        }
+
+        let grid_size = Vec2::new(max_columns as f32, columns_per_row.len() as f32);
+        // `current_padding.x` only reflects whichever row the loop above finished on,
+        // not the widest row, so the x-padding used for `size` is derived from
+        // `max_columns` instead.
+        let size_padding = Vec2::new(
+            if max_columns > 1 { spacing.x } else { 0.0 },
+            current_padding.y,
+        );
+
+        Self {
+            size: ((tile_size + size_padding) * grid_size) - size_padding,
+            textures: sprites,
+            texture_handles: None,
+            texture_names: None,
+            source_rects: None,
         }
     }
 
@@ -123,4 +211,36 @@ impl TextureAtlasLayout {
             .as_ref()
             .and_then(|texture_handles| texture_handles.get(texture).cloned())
     }
+
+    /// Retrieves the texture *section* index of the given frame `name`.
+    ///
+    /// This requires the layout to have been built by a loader that records
+    /// frame names, such as [`TextureAtlasLayoutLoader`].
+    ///
+    /// [`TextureAtlasLayoutLoader`]: crate::TextureAtlasLayoutLoader
+    pub fn get_texture_index_by_name(&self, name: &str) -> Option<usize> {
+        self.texture_names
+            .as_ref()
+            .and_then(|texture_names| texture_names.get(name).cloned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_grid_with_layout_size_uses_widest_row() {
+        // A 3-column row followed by a shorter 1-column row: the atlas must still be
+        // as wide as the widest row, even though the loop finishes on the narrow one.
+        let layout = TextureAtlasLayout::from_grid_with_layout(
+            Vec2::new(10.0, 10.0),
+            &[3, 1],
+            Some(Vec2::new(2.0, 2.0)),
+            None,
+        );
+
+        assert_eq!(layout.size, Vec2::new(34.0, 22.0));
+        assert_eq!(layout.len(), 4);
+    }
 }
@@ -0,0 +1,393 @@
+use crate::texture_atlas_layout::TextureAtlasLayout;
+use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy_math::{Rect, Vec2};
+use bevy_utils::{BoxedFuture, HashMap};
+use serde::Deserialize;
+use thiserror::Error;
+
+/// Loads [`TextureAtlasLayout`]s from the atlas description formats exported by common
+/// sprite packing tools, so hand-packed sheets don't have to be re-expressed as a
+/// uniform grid via [`TextureAtlasLayout::from_grid`].
+///
+/// Supports the TexturePacker JSON formats (both the `frames` object/"hash" variant and
+/// the `frames` array variant) and the LibGDX `.atlas` text format. Multi-page LibGDX
+/// atlases aren't supported; split each page into its own `.atlas`/image pair.
+///
+/// [`TextureAtlasLayout::from_grid`]: crate::TextureAtlasLayout::from_grid
+#[derive(Default)]
+pub struct TextureAtlasLayoutLoader;
+
+/// An error that occurs when loading a [`TextureAtlasLayout`] from a packer description
+/// fails.
+#[derive(Error, Debug)]
+pub enum TextureAtlasLayoutLoaderError {
+    /// A conversion error from the TexturePacker JSON format failed.
+    #[error("could not parse TexturePacker JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    /// A conversion error from the LibGDX `.atlas` text format failed.
+    #[error("could not parse LibGDX atlas description: {0}")]
+    Atlas(String),
+}
+
+impl AssetLoader for TextureAtlasLayoutLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+        Box::pin(async move {
+            let extension = load_context.path().extension().and_then(|e| e.to_str());
+            let layout = if extension == Some("atlas") {
+                parse_libgdx_atlas(std::str::from_utf8(bytes)?)?
+            } else {
+                parse_texture_packer_json(bytes)?
+            };
+            load_context.set_default_asset(LoadedAsset::new(layout));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["atlas", "tpsheet", "tpjson"]
+    }
+}
+
+#[derive(Deserialize)]
+struct TpFrameRect {
+    x: f32,
+    y: f32,
+    w: f32,
+    h: f32,
+}
+
+#[derive(Deserialize)]
+struct TpSize {
+    w: f32,
+    h: f32,
+}
+
+#[derive(Deserialize)]
+struct TpFrame {
+    frame: TpFrameRect,
+    #[serde(default)]
+    rotated: bool,
+    #[serde(default)]
+    trimmed: bool,
+    #[serde(rename = "spriteSourceSize", default)]
+    sprite_source_size: Option<TpFrameRect>,
+    #[serde(rename = "sourceSize", default)]
+    source_size: Option<TpSize>,
+}
+
+#[derive(Deserialize)]
+struct TpFrameArrayEntry {
+    filename: String,
+    #[serde(flatten)]
+    frame: TpFrame,
+}
+
+#[derive(Deserialize)]
+struct TpMeta {
+    size: TpSize,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum TpFrames {
+    Hash(HashMap<String, TpFrame>),
+    Array(Vec<TpFrameArrayEntry>),
+}
+
+#[derive(Deserialize)]
+struct TpManifest {
+    frames: TpFrames,
+    meta: TpMeta,
+}
+
+fn parse_texture_packer_json(
+    bytes: &[u8],
+) -> Result<TextureAtlasLayout, TextureAtlasLayoutLoaderError> {
+    let manifest: TpManifest = serde_json::from_slice(bytes)?;
+
+    let mut layout =
+        TextureAtlasLayout::new_empty(Vec2::new(manifest.meta.size.w, manifest.meta.size.h));
+    let mut texture_names = HashMap::default();
+    let mut source_rects = HashMap::default();
+
+    let named_frames: Vec<(String, TpFrame)> = match manifest.frames {
+        TpFrames::Hash(map) => map.into_iter().collect(),
+        TpFrames::Array(entries) => entries
+            .into_iter()
+            .map(|entry| (entry.filename, entry.frame))
+            .collect(),
+    };
+
+    for (name, frame) in named_frames {
+        let rect = tp_frame_to_rect(&frame);
+        let index = layout.add_texture(rect);
+        if let Some(source_rect) = tp_frame_to_source_rect(&frame) {
+            source_rects.insert(index, source_rect);
+        }
+        texture_names.insert(name, index);
+    }
+
+    layout.texture_names = Some(texture_names);
+    if !source_rects.is_empty() {
+        layout.source_rects = Some(source_rects);
+    }
+    Ok(layout)
+}
+
+/// Converts a single TexturePacker frame entry into the [`Rect`] stored in the atlas,
+/// i.e. where the (possibly trimmed and rotated) section lives in the packed texture.
+/// Rotated frames are packed sideways, so their width and height are swapped back here.
+fn tp_frame_to_rect(frame: &TpFrame) -> Rect {
+    let (w, h) = if frame.rotated {
+        (frame.frame.h, frame.frame.w)
+    } else {
+        (frame.frame.w, frame.frame.h)
+    };
+    let min = Vec2::new(frame.frame.x, frame.frame.y);
+    Rect {
+        min,
+        max: min + Vec2::new(w, h),
+    }
+}
+
+/// For a trimmed frame, converts `spriteSourceSize`/`sourceSize` into the section's rect
+/// within its original, untrimmed source image. Returns `None` for frames that weren't
+/// trimmed, since their packed rect already covers the whole source image.
+///
+/// The rect is clipped to `sourceSize`, the untrimmed canvas the frame was cut from, in
+/// case a malformed manifest reports a `spriteSourceSize` that runs past its bounds.
+fn tp_frame_to_source_rect(frame: &TpFrame) -> Option<Rect> {
+    if !frame.trimmed {
+        return None;
+    }
+    let sprite_source_size = frame.sprite_source_size.as_ref()?;
+    let min = Vec2::new(sprite_source_size.x, sprite_source_size.y);
+    let mut max = min + Vec2::new(sprite_source_size.w, sprite_source_size.h);
+    if let Some(source_size) = &frame.source_size {
+        max = max.min(Vec2::new(source_size.w, source_size.h));
+    }
+    Some(Rect { min, max })
+}
+
+/// One parsed LibGDX atlas region, before its index in the merged layout is known.
+struct GdxRegion {
+    name: String,
+    xy: Vec2,
+    size: Vec2,
+    rotated: bool,
+    orig: Option<Vec2>,
+    offset: Option<Vec2>,
+}
+
+/// Parses the LibGDX `.atlas` text format.
+///
+/// The format is a single page: an image file name line, followed by `key: value` page
+/// attributes, followed by one block per region: a name line followed by its
+/// `key: value` attributes (`rotate`, `xy`, `size`, `orig`, `offset`, ...). Multi-page
+/// atlases, which repeat this whole structure, are rejected with a clear error rather
+/// than having their second page misparsed as a malformed region.
+fn parse_libgdx_atlas(
+    text: &str,
+) -> Result<TextureAtlasLayout, TextureAtlasLayoutLoaderError> {
+    let lines: Vec<&str> = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+
+    // The first line is the backing page image's file name.
+    if lines.is_empty() {
+        return Err(TextureAtlasLayoutLoaderError::Atlas(
+            "empty atlas file".to_string(),
+        ));
+    }
+    let mut index = 1;
+
+    let mut page_size = None;
+    while index < lines.len() {
+        let Some((key, value)) = lines[index].split_once(':') else {
+            break;
+        };
+        if key.trim() == "size" {
+            page_size = Some(parse_xy(value.trim(), "size")?);
+        }
+        index += 1;
+    }
+    let page_size = page_size
+        .ok_or_else(|| TextureAtlasLayoutLoaderError::Atlas("page has no `size`".to_string()))?;
+
+    let mut regions = Vec::new();
+    while index < lines.len() {
+        let name = lines[index].to_string();
+        index += 1;
+
+        // A page header's very first attribute is always `size` (see the page-header
+        // loop above), while a region's first attribute is one of `rotate`/`xy`/`orig`/
+        // `offset`/`index` — it never leads with `size`. So a `size:` line immediately
+        // after `name` means `name` was actually the next page's image file name, not
+        // a region.
+        if lines
+            .get(index)
+            .and_then(|line| line.split_once(':'))
+            .is_some_and(|(key, _)| key.trim() == "size")
+        {
+            return Err(TextureAtlasLayoutLoaderError::Atlas(
+                "multi-page atlases aren't supported".to_string(),
+            ));
+        }
+
+        let mut xy = None;
+        let mut size = None;
+        let mut rotated = false;
+        let mut orig = None;
+        let mut offset = None;
+        while index < lines.len() {
+            let Some((key, value)) = lines[index].split_once(':') else {
+                break;
+            };
+            let value = value.trim();
+            match key.trim() {
+                "xy" => xy = Some(parse_xy(value, "xy")?),
+                "size" => size = Some(parse_xy(value, "size")?),
+                "rotate" => rotated = value == "true" || value == "90",
+                "orig" => orig = Some(parse_xy(value, "orig")?),
+                "offset" => offset = Some(parse_xy(value, "offset")?),
+                // `format`, `filter`, `repeat`, `index` and similar attributes don't
+                // affect where the region lives in the page, so they're ignored here.
+                _ => {}
+            }
+            index += 1;
+        }
+
+        let xy = xy.ok_or_else(|| {
+            TextureAtlasLayoutLoaderError::Atlas(format!("region `{name}` has no `xy`"))
+        })?;
+        let size = size.ok_or_else(|| {
+            TextureAtlasLayoutLoaderError::Atlas(format!("region `{name}` has no `size`"))
+        })?;
+        regions.push(GdxRegion {
+            name,
+            xy,
+            size,
+            rotated,
+            orig,
+            offset,
+        });
+    }
+
+    let mut layout = TextureAtlasLayout::new_empty(page_size);
+    let mut texture_names = HashMap::default();
+    let mut source_rects = HashMap::default();
+
+    for region in regions {
+        let size = if region.rotated {
+            Vec2::new(region.size.y, region.size.x)
+        } else {
+            region.size
+        };
+        let index = layout.add_texture(Rect {
+            min: region.xy,
+            max: region.xy + size,
+        });
+        if let (Some(orig), Some(offset)) = (region.orig, region.offset) {
+            source_rects.insert(
+                index,
+                Rect {
+                    min: offset,
+                    max: offset + region.size,
+                },
+            );
+        }
+        texture_names.insert(region.name, index);
+    }
+
+    layout.texture_names = Some(texture_names);
+    if !source_rects.is_empty() {
+        layout.source_rects = Some(source_rects);
+    }
+    Ok(layout)
+}
+
+fn parse_xy(value: &str, field: &str) -> Result<Vec2, TextureAtlasLayoutLoaderError> {
+    let mut parts = value.split(',').map(str::trim);
+    let x = parts
+        .next()
+        .and_then(|v| v.parse::<f32>().ok())
+        .ok_or_else(|| TextureAtlasLayoutLoaderError::Atlas(format!("invalid `{field}`: {value}")))?;
+    let y = parts
+        .next()
+        .and_then(|v| v.parse::<f32>().ok())
+        .ok_or_else(|| TextureAtlasLayoutLoaderError::Atlas(format!("invalid `{field}`: {value}")))?;
+    Ok(Vec2::new(x, y))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_page_atlas_parses_regions() {
+        let atlas = "\
+sheet.png
+size: 64,64
+format: RGBA8888
+filter: Nearest,Nearest
+repeat: none
+hero/idle
+  rotate: false
+  xy: 2, 2
+  size: 16, 16
+  orig: 16, 16
+  offset: 0, 0
+  index: -1
+";
+
+        let layout = parse_libgdx_atlas(atlas).unwrap();
+
+        assert_eq!(layout.size, Vec2::new(64.0, 64.0));
+        assert_eq!(layout.get_texture_index_by_name("hero/idle"), Some(0));
+    }
+
+    #[test]
+    fn multi_page_atlas_is_rejected_with_a_clear_error() {
+        let atlas = "\
+sheet1.png
+size: 64,64
+format: RGBA8888
+filter: Nearest,Nearest
+repeat: none
+hero/idle
+  rotate: false
+  xy: 2, 2
+  size: 16, 16
+  orig: 16, 16
+  offset: 0, 0
+  index: -1
+sheet2.png
+size: 64,64
+format: RGBA8888
+filter: Nearest,Nearest
+repeat: none
+hero/walk
+  rotate: false
+  xy: 2, 2
+  size: 16, 16
+  orig: 16, 16
+  offset: 0, 0
+  index: -1
+";
+
+        let err = parse_libgdx_atlas(atlas).unwrap_err();
+
+        match err {
+            TextureAtlasLayoutLoaderError::Atlas(message) => {
+                assert_eq!(message, "multi-page atlases aren't supported");
+            }
+            other => panic!("expected an Atlas error, got {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,358 @@
+use crate::texture_atlas_layout::TextureAtlasLayout;
+use bevy_math::{Rect, Vec2};
+use bevy_render::{
+    render_resource::{Extent3d, TextureDimension, TextureFormat},
+    texture::Image,
+};
+use bevy_utils::HashMap;
+use thiserror::Error;
+
+/// An error returned by [`merge_texture_atlases`].
+#[derive(Error, Debug)]
+pub enum TextureAtlasMergeError {
+    /// The packed rectangles don't fit within `max_size` at any arrangement this packer
+    /// tries.
+    #[error(
+        "merged atlas would require at least {required:?}, which exceeds the maximum size {max:?}"
+    )]
+    ExceedsMaxSize {
+        /// A lower bound on the size needed: the size of a single source rect wider than
+        /// `max_size`, or the bounding box of the rects placed before packing stopped.
+        required: Vec2,
+        /// The caller-supplied maximum size.
+        max: Vec2,
+    },
+    /// One of the source images isn't in a format this blitter knows how to copy.
+    #[error("image format {0:?} is not supported for atlas merging")]
+    UnsupportedFormat(TextureFormat),
+    /// The source images don't all use the same format, so there's no single format to
+    /// give the merged image without silently reinterpreting some of their bytes.
+    #[error("sources have mismatched formats {first:?} and {other:?}; merge sources with the same format, or convert them first")]
+    MismatchedFormats {
+        /// The format of the first source image.
+        first: TextureFormat,
+        /// The format of a later source image that didn't match.
+        other: TextureFormat,
+    },
+}
+
+/// Identifies a texture *section* in one of the source layouts passed to
+/// [`merge_texture_atlases`]: the index of the layout within the input slice, and the
+/// index of the section within that layout.
+pub type SourceTextureIndex = (usize, usize);
+
+/// Combines several [`TextureAtlasLayout`]s and their backing [`Image`]s into a single
+/// merged layout and image, running a rectangle-packing pass over every source rect.
+///
+/// This is useful when combining modded/DLC sprite sheets or atlases that were loaded
+/// separately at runtime, so they can share one draw call / bind group instead of one
+/// each. Returns the merged layout, the merged image, and a table mapping each
+/// `(source layout index, source section index)` to its index in the merged layout.
+///
+/// Fails if the packed result would need more than `max_size` in either dimension, if
+/// any source image isn't in [`TextureFormat::Rgba8UnormSrgb`] or
+/// [`TextureFormat::Rgba8Unorm`] (the only formats this blitter currently understands),
+/// or if the sources don't all share the same format — merging, say, a `Rgba8Unorm`
+/// (linear) source with a `Rgba8UnormSrgb` one would silently reinterpret one of their
+/// byte buffers under the other's color space without any actual conversion.
+pub fn merge_texture_atlases(
+    sources: &[(&TextureAtlasLayout, &Image)],
+    max_size: Vec2,
+) -> Result<(TextureAtlasLayout, Image, HashMap<SourceTextureIndex, usize>), TextureAtlasMergeError>
+{
+    let mut format = None;
+    for (_, image) in sources {
+        let image_format = image.texture_descriptor.format;
+        if image_format != TextureFormat::Rgba8UnormSrgb && image_format != TextureFormat::Rgba8Unorm
+        {
+            return Err(TextureAtlasMergeError::UnsupportedFormat(image_format));
+        }
+        match format {
+            None => format = Some(image_format),
+            Some(first) if first != image_format => {
+                return Err(TextureAtlasMergeError::MismatchedFormats {
+                    first,
+                    other: image_format,
+                });
+            }
+            Some(_) => {}
+        }
+    }
+    let format = format.unwrap_or(TextureFormat::Rgba8UnormSrgb);
+
+    let mut rects_to_place = Vec::new();
+    for (layout_index, (layout, _)) in sources.iter().enumerate() {
+        for (section_index, rect) in layout.textures.iter().enumerate() {
+            rects_to_place.push(((layout_index, section_index), rect.size()));
+        }
+    }
+
+    let (packed_size, placements) = pack_rects_skyline(&rects_to_place, max_size)?;
+
+    let merged_width = packed_size.x as u32;
+    let merged_height = packed_size.y as u32;
+    let mut merged_data = vec![0u8; (merged_width * merged_height * 4) as usize];
+
+    let mut merged_textures = Vec::with_capacity(rects_to_place.len());
+    let mut remap = HashMap::default();
+
+    for (source_index, placed_min) in placements {
+        let (layout_index, section_index) = source_index;
+        let (layout, image) = sources[layout_index];
+        let source_rect = layout.textures[section_index];
+
+        blit_rect(
+            &image.data,
+            image.texture_descriptor.size.width,
+            source_rect,
+            &mut merged_data,
+            merged_width,
+            placed_min,
+        );
+
+        let new_index = merged_textures.len();
+        merged_textures.push(Rect {
+            min: placed_min,
+            max: placed_min + source_rect.size(),
+        });
+        remap.insert(source_index, new_index);
+    }
+
+    let merged_image = Image::new(
+        Extent3d {
+            width: merged_width,
+            height: merged_height,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        merged_data,
+        format,
+    );
+
+    let merged_layout = TextureAtlasLayout {
+        size: packed_size,
+        textures: merged_textures,
+        texture_handles: None,
+        texture_names: None,
+        source_rects: None,
+    };
+
+    Ok((merged_layout, merged_image, remap))
+}
+
+/// Copies one `source_rect` region of a RGBA8 image into `dest` at `dest_min`, given
+/// the stride (`source_width`/`dest_width`, in pixels) of each buffer.
+fn blit_rect(
+    source: &[u8],
+    source_width: u32,
+    source_rect: Rect,
+    dest: &mut [u8],
+    dest_width: u32,
+    dest_min: Vec2,
+) {
+    const BYTES_PER_PIXEL: u32 = 4;
+    let width = (source_rect.max.x - source_rect.min.x) as u32;
+    let height = (source_rect.max.y - source_rect.min.y) as u32;
+    let src_x0 = source_rect.min.x as u32;
+    let src_y0 = source_rect.min.y as u32;
+    let dst_x0 = dest_min.x as u32;
+    let dst_y0 = dest_min.y as u32;
+
+    for row in 0..height {
+        let src_offset =
+            (((src_y0 + row) * source_width + src_x0) * BYTES_PER_PIXEL) as usize;
+        let dst_offset = (((dst_y0 + row) * dest_width + dst_x0) * BYTES_PER_PIXEL) as usize;
+        let row_bytes = (width * BYTES_PER_PIXEL) as usize;
+        dest[dst_offset..dst_offset + row_bytes]
+            .copy_from_slice(&source[src_offset..src_offset + row_bytes]);
+    }
+}
+
+/// A minimal skyline bottom-left rectangle packer: rects are placed widest-first along
+/// a horizontal skyline, each one going wherever it creates the least additional
+/// height. `max_size` is only a ceiling on how wide/tall the packing is allowed to
+/// grow; the returned size is the actual bounding box of where rects landed, which is
+/// usually much smaller.
+fn pack_rects_skyline(
+    rects: &[(SourceTextureIndex, Vec2)],
+    max_size: Vec2,
+) -> Result<(Vec2, Vec<(SourceTextureIndex, Vec2)>), TextureAtlasMergeError> {
+    let mut order: Vec<usize> = (0..rects.len()).collect();
+    order.sort_by(|&a, &b| rects[b].1.x.partial_cmp(&rects[a].1.x).unwrap());
+
+    let max_width = max_size.x as u32;
+    // One skyline entry per (x, height) segment, starting as a single flat segment.
+    let mut skyline: Vec<(u32, u32)> = vec![(0, 0)];
+    let mut placements = Vec::with_capacity(rects.len());
+    let mut used_width = 0u32;
+    let mut used_height = 0u32;
+
+    for index in order {
+        let (source_index, size) = rects[index];
+        let w = size.x as u32;
+        let h = size.y as u32;
+        if w > max_width {
+            return Err(TextureAtlasMergeError::ExceedsMaxSize {
+                required: Vec2::new(w as f32, h as f32),
+                max: max_size,
+            });
+        }
+
+        let (best_x, best_y) = find_skyline_position(&skyline, max_width, w);
+        used_width = used_width.max(best_x + w);
+        used_height = used_height.max(best_y + h);
+        if used_height as f32 > max_size.y {
+            return Err(TextureAtlasMergeError::ExceedsMaxSize {
+                required: Vec2::new(used_width as f32, used_height as f32),
+                max: max_size,
+            });
+        }
+
+        update_skyline(&mut skyline, best_x, best_x + w, best_y + h);
+        placements.push((source_index, Vec2::new(best_x as f32, best_y as f32)));
+    }
+
+    Ok((Vec2::new(used_width as f32, used_height as f32), placements))
+}
+
+/// Finds the lowest-height `x` position along the skyline that fits a rect of
+/// width `w`, breaking ties by smallest `x` (bottom-left rule).
+fn find_skyline_position(skyline: &[(u32, u32)], total_width: u32, w: u32) -> (u32, u32) {
+    let mut best = (0u32, u32::MAX);
+    for &(start_x, _) in skyline {
+        if start_x + w > total_width {
+            continue;
+        }
+        let height = skyline
+            .iter()
+            .filter(|&&(x, _)| x >= start_x && x < start_x + w)
+            .map(|&(_, h)| h)
+            .max()
+            .unwrap_or(0);
+        if height < best.1 {
+            best = (start_x, height);
+        }
+    }
+    best
+}
+
+fn update_skyline(skyline: &mut Vec<(u32, u32)>, start_x: u32, end_x: u32, height: u32) {
+    // The segment covering `end_x` keeps its height to the right of the placed rect.
+    let height_after = skyline
+        .iter()
+        .filter(|&&(x, _)| x <= end_x)
+        .last()
+        .map_or(0, |&(_, h)| h);
+    skyline.retain(|&(x, _)| x < start_x || x >= end_x);
+    skyline.push((start_x, height));
+    skyline.push((end_x, height_after));
+    skyline.sort_by_key(|&(x, _)| x);
+    skyline.dedup_by_key(|&mut (x, _)| x);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solid_image(width: u32, height: u32, rgba: [u8; 4], format: TextureFormat) -> Image {
+        Image::new_fill(
+            Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &rgba,
+            format,
+        )
+    }
+
+    #[test]
+    fn merge_packs_and_remaps_every_source_texture() {
+        let layout_a = TextureAtlasLayout::from_grid(Vec2::new(4.0, 4.0), 2, 1, None, None);
+        let image_a = solid_image(8, 4, [255, 0, 0, 255], TextureFormat::Rgba8UnormSrgb);
+
+        let layout_b = TextureAtlasLayout::from_grid(Vec2::new(4.0, 4.0), 1, 1, None, None);
+        let image_b = solid_image(4, 4, [0, 255, 0, 255], TextureFormat::Rgba8UnormSrgb);
+
+        let (merged_layout, merged_image, remap) = merge_texture_atlases(
+            &[(&layout_a, &image_a), (&layout_b, &image_b)],
+            Vec2::new(256.0, 256.0),
+        )
+        .unwrap();
+
+        assert_eq!(merged_layout.len(), 3);
+        assert_eq!(remap.len(), 3);
+        assert_eq!(
+            merged_image.texture_descriptor.format,
+            TextureFormat::Rgba8UnormSrgb
+        );
+
+        // Every remapped index must address a pixel that still holds its source color.
+        for ((layout_index, section_index), new_index) in &remap {
+            let expected = if *layout_index == 0 {
+                [255, 0, 0, 255]
+            } else {
+                [0, 255, 0, 255]
+            };
+            let rect = merged_layout.textures[*new_index];
+            let x = rect.min.x as u32;
+            let y = rect.min.y as u32;
+            let stride = merged_image.texture_descriptor.size.width;
+            let offset = ((y * stride + x) * 4) as usize;
+            assert_eq!(&merged_image.data[offset..offset + 4], expected);
+            let _ = section_index;
+        }
+    }
+
+    #[test]
+    fn merge_shrinks_output_to_the_packed_bounding_box() {
+        let layout_a = TextureAtlasLayout::from_grid(Vec2::new(4.0, 4.0), 2, 1, None, None);
+        let image_a = solid_image(8, 4, [255, 0, 0, 255], TextureFormat::Rgba8UnormSrgb);
+
+        let layout_b = TextureAtlasLayout::from_grid(Vec2::new(4.0, 4.0), 1, 1, None, None);
+        let image_b = solid_image(4, 4, [0, 255, 0, 255], TextureFormat::Rgba8UnormSrgb);
+
+        // Three 4x4 rects packed widest-first fit in a 12x4 box; max_size is a ceiling,
+        // not the allocated size.
+        let (merged_layout, merged_image, _remap) = merge_texture_atlases(
+            &[(&layout_a, &image_a), (&layout_b, &image_b)],
+            Vec2::new(256.0, 256.0),
+        )
+        .unwrap();
+
+        assert_eq!(merged_layout.size, Vec2::new(12.0, 4.0));
+        assert_eq!(merged_image.texture_descriptor.size.width, 12);
+        assert_eq!(merged_image.texture_descriptor.size.height, 4);
+    }
+
+    #[test]
+    fn merge_rejects_mismatched_formats() {
+        let layout = TextureAtlasLayout::from_grid(Vec2::new(4.0, 4.0), 1, 1, None, None);
+        let srgb_image = solid_image(4, 4, [1, 2, 3, 255], TextureFormat::Rgba8UnormSrgb);
+        let unorm_image = solid_image(4, 4, [1, 2, 3, 255], TextureFormat::Rgba8Unorm);
+
+        let result = merge_texture_atlases(
+            &[(&layout, &srgb_image), (&layout, &unorm_image)],
+            Vec2::new(256.0, 256.0),
+        );
+
+        assert!(matches!(
+            result,
+            Err(TextureAtlasMergeError::MismatchedFormats { .. })
+        ));
+    }
+
+    #[test]
+    fn merge_rejects_oversized_result() {
+        let layout = TextureAtlasLayout::from_grid(Vec2::new(64.0, 64.0), 4, 4, None, None);
+        let image = solid_image(256, 256, [0, 0, 0, 255], TextureFormat::Rgba8UnormSrgb);
+
+        let result = merge_texture_atlases(&[(&layout, &image)], Vec2::new(32.0, 32.0));
+
+        assert!(matches!(
+            result,
+            Err(TextureAtlasMergeError::ExceedsMaxSize { .. })
+        ));
+    }
+}